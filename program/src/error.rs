@@ -9,6 +9,33 @@ use {
 pub enum NameServiceError {
     #[error("out of space")]
     OutOfSpace = 0,
+
+    #[error("account is already initialized")]
+    AlreadyInitialized = 1,
+
+    #[error("account is not initialized")]
+    Uninitialized = 2,
+
+    #[error("invalid record owner")]
+    InvalidOwner = 3,
+
+    #[error("invalid class signer")]
+    InvalidClassSigner = 4,
+
+    #[error("invalid parent owner")]
+    InvalidParentOwner = 5,
+
+    #[error("account is not rent exempt")]
+    NotRentExempt = 6,
+
+    #[error("write offset is out of bounds")]
+    OffsetOutOfBounds = 7,
+
+    #[error("numeric overflow")]
+    NumericOverflow = 8,
+
+    #[error("unknown account data version")]
+    UnknownDataVersion = 9,
 }
 
 // Result 별칭 (필요에 따라 제네릭 기본값으로 확장 가능)
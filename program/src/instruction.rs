@@ -1,7 +1,11 @@
 use {
+    crate::state::get_seeds_and_key,
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
         instruction::{AccountMeta, Instruction},
+        program::{invoke, invoke_signed},
         program_error::ProgramError,
         pubkey::Pubkey,
         system_program,
@@ -71,6 +75,32 @@ pub enum NameRegistryInstruction {
     /// 1. '[signer]' 계정 소유자
     /// 2. '[writable]' 환불 받을 계정
     Delete,
+
+    /// Migrate: 저장된 `data_version`을 읽어 현재 버전보다 낮으면
+    /// 최신 레이아웃으로 재작성한다. `owner`/`class`/`parent_name`은 보존하고
+    /// 새로 추가된 필드는 0으로 채운 뒤 버전 바이트를 올린다.
+    /// v0에는 버전 바이트가 없어 자식 레코드는 v0/v1을 구분할 수 없으므로
+    /// 최상위 레코드(`parent_name == default`)만 마이그레이션할 수 있다.
+    /// 기대하는 계정들:
+    /// 0. '[writable]' 마이그레이션할 이름 레코드
+    /// 1. '[writable, signer]' 렌트 차액을 댈 자금 제공자(Funding) 계정
+    /// 2. '[]' 시스템 프로그램
+    Migrate,
+
+    /// Realloc: 이름 레코드의 데이터 영역을 키우거나 줄인다.
+    /// `new_space`는 런타임 한계인 10 MiB(`MAX_PERMITTED_DATA_LENGTH`)로 제한되며,
+    /// 새 크기의 렌트 면제 최소치를 계산해 커질 때는 자금 계정에서 차액을 받고,
+    /// 줄어들 때는 대상 계정으로 환불한다. 새로 추가된 바이트는 0으로 초기화한다.
+    /// 권한 검사는 `Update`와 동일하다(소유자/클래스/부모 소유자 서명자).
+    /// 기대하는 계정들:
+    /// 0. '[writable]' 크기를 조정할 이름 레코드
+    /// 1. '[writable, signer]' 자금 제공자(커질 때) 또는 환불 대상(줄어들 때) 계정
+    /// 2. '[]' 시스템 프로그램
+    /// 3. '[signer]' 업데이트 권한자 (소유자/클래스/부모 소유자)
+    /// 4. '[]' 부모 이름 레코드 (Case 3에서만 필요)
+    Realloc {
+        new_space: u32, // 새 데이터 영역 크기
+    },
 }
 
 /// Create 명령용 Instruction 생성기
@@ -83,7 +113,21 @@ pub fn create(
     name_class_opt: Option<Pubkey>,            // 선택적 클래스 계정(서명자)
     name_parent_opt: Option<Pubkey>,           // 선택적 부모 이름 레코드(서명자 아님)
     name_parent_owner_opt: Option<Pubkey>,     // 선택적 부모 소유자(서명자)
-) -> Result<Instruction, ProgramError> {
+) -> Result<(Instruction, u8), ProgramError> {
+    // 명령 데이터에서 hashed_name을 꺼내 PDA bump를 유도한다. 이렇게 하면 호출부가
+    // bump를 오프체인에서 따로 계산하지 않고 빌더가 돌려주는 값을 그대로 쓸 수 있다
+    // ("accounts context가 찾아준 bump" 방식과 동일한 사용감).
+    let hashed_name = match &instruction_data {
+        NameRegistryInstruction::Create { hashed_name, .. } => hashed_name.clone(),
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+    let (_derived_key, _seeds, bump) = get_seeds_and_key(
+        &name_service_program_id,
+        hashed_name,
+        name_class_opt.as_ref(),
+        name_parent_opt.as_ref(),
+    );
+
     // Borsh 직렬화
     let data = instruction_data.try_to_vec().unwrap();
 
@@ -117,11 +161,14 @@ pub fn create(
         accounts.push(AccountMeta::new_readonly(Pubkey::default(), false));
     }
 
-    Ok(Instruction {
-        program_id: name_service_program_id,
-        accounts,
-        data,
-    })
+    Ok((
+        Instruction {
+            program_id: name_service_program_id,
+            accounts,
+            data,
+        },
+        bump,
+    ))
 }
 
 /// Update 명령용 Instruction 생성기
@@ -183,6 +230,176 @@ pub fn transfer(
     })
 }
 
+/// Migrate 명령용 Instruction 생성기
+pub fn migrate(
+    name_service_program_id: Pubkey,
+    name_account_key: Pubkey, // 마이그레이션할 이름 레코드 (writable)
+    payer_key: Pubkey,        // 렌트 차액을 댈 자금 제공자 (writable, signer)
+) -> Result<Instruction, ProgramError> {
+    let instruction_data = NameRegistryInstruction::Migrate;
+    let data = instruction_data.try_to_vec().unwrap();
+
+    let accounts = vec![
+        AccountMeta::new(name_account_key, false),
+        AccountMeta::new(payer_key, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: name_service_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Realloc 명령용 Instruction 생성기
+pub fn realloc(
+    name_service_program_id: Pubkey, // 프로그램 ID
+    new_space: u32,                  // 새 데이터 영역 크기
+    name_account_key: Pubkey,        // 대상 이름 레코드 (writable)
+    payer_key: Pubkey,               // 자금 제공자/환불 대상 (writable, signer)
+    name_update_signer: Pubkey,      // 업데이트 권한 서명자(Owner/Class/Parent Owner 중 하나)
+    name_parent_opt: Option<Pubkey>, // Case 3에서 필요한 부모 이름 레코드
+) -> Result<Instruction, ProgramError> {
+    let instruction_data = NameRegistryInstruction::Realloc { new_space };
+    let data = instruction_data.try_to_vec().unwrap();
+
+    let mut accounts: Vec<AccountMeta> = vec![
+        AccountMeta::new(name_account_key, false),     // [writable] 이름 레코드
+        AccountMeta::new(payer_key, true),             // [writable, signer] 자금/환불 계정
+        AccountMeta::new_readonly(system_program::id(), false), // 시스템 프로그램
+        AccountMeta::new_readonly(name_update_signer, true),    // [signer] 업데이트 권한자
+    ];
+
+    // Case 3: 부모 이름 레코드가 필요한 경우
+    if let Some(name_parent_key) = name_parent_opt {
+        accounts.push(AccountMeta::new_readonly(name_parent_key, false));
+    }
+
+    Ok(Instruction {
+        program_id: name_service_program_id,
+        accounts,
+        data,
+    })
+}
+
+// === CPI 헬퍼 ===============================================================
+//
+// 위 빌더들은 최상위 트랜잭션용 `Instruction` 값만 만들기 때문에, 자신의 PDA가
+// 레코드의 `owner`/`class`인 호출 프로그램이 한 트랜잭션 안에서 레코드를 직접
+// 생성·수정·이전·삭제할 수 없다. 아래 헬퍼들은 `AccountInfo` 슬라이스를 받아
+// 이 프로그램을 `invoke`/`invoke_signed`로 호출한다. `account_infos`는 대응하는
+// 빌더가 만드는 `AccountMeta` 순서와 같은 순서로 넘겨야 한다.
+//
+// PDA 서명 시드를 넘기는 헬퍼는 `transfer_signed`뿐이다. `create_invoke`/`update_invoke`/
+// `transfer_invoke`/`delete_invoke`는 `invoke`를 쓰므로, 필요한 서명자가 바깥 트랜잭션에서
+// 이미 서명한 경우에만 성공하며 PDA가 서명자인 경우에는 쓸 수 없다.
+
+/// `create`를 CPI로 호출한다.
+#[allow(clippy::too_many_arguments)]
+pub fn create_invoke<'a>(
+    name_service_program_id: Pubkey,
+    instruction_data: NameRegistryInstruction,
+    name_account_key: Pubkey,
+    name_owner: Pubkey,
+    payer_key: Pubkey,
+    name_class_opt: Option<Pubkey>,
+    name_parent_opt: Option<Pubkey>,
+    name_parent_owner_opt: Option<Pubkey>,
+    account_infos: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let (ix, _bump) = create(
+        name_service_program_id,
+        instruction_data,
+        name_account_key,
+        name_owner,
+        payer_key,
+        name_class_opt,
+        name_parent_opt,
+        name_parent_owner_opt,
+    )?;
+    invoke(&ix, account_infos)
+}
+
+/// `update`를 CPI로 호출한다.
+pub fn update_invoke<'a>(
+    name_service_program_id: Pubkey,
+    offset: u32,
+    data: Vec<u8>,
+    name_account_key: Pubkey,
+    name_update_signer: Pubkey,
+    name_parent_opt: Option<Pubkey>,
+    account_infos: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let ix = update(
+        name_service_program_id,
+        offset,
+        data,
+        name_account_key,
+        name_update_signer,
+        name_parent_opt,
+    )?;
+    invoke(&ix, account_infos)
+}
+
+/// `transfer`를 CPI로 호출한다.
+pub fn transfer_invoke<'a>(
+    name_service_program_id: Pubkey,
+    name_owner: Pubkey,
+    name_account_key: Pubkey,
+    name_owner_key: Pubkey,
+    name_parent: Option<Pubkey>,
+    account_infos: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let ix = transfer(
+        name_service_program_id,
+        name_owner,
+        name_account_key,
+        name_owner_key,
+        name_parent,
+    )?;
+    invoke(&ix, account_infos)
+}
+
+/// `transfer`를 PDA 서명 시드와 함께 CPI로 호출한다.
+/// 에스크로/볼트형 프로그램이 자신의 PDA가 현재 소유자인 레코드를 더 큰 명령의
+/// 일부로 원자적으로 재배정할 수 있게 한다.
+pub fn transfer_signed<'a>(
+    name_service_program_id: Pubkey,
+    name_owner: Pubkey,
+    name_account_key: Pubkey,
+    name_owner_key: Pubkey,
+    name_parent: Option<Pubkey>,
+    account_infos: &[AccountInfo<'a>],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = transfer(
+        name_service_program_id,
+        name_owner,
+        name_account_key,
+        name_owner_key,
+        name_parent,
+    )?;
+    invoke_signed(&ix, account_infos, signers_seeds)
+}
+
+/// `delete`를 CPI로 호출한다.
+pub fn delete_invoke<'a>(
+    name_service_program_id: Pubkey,
+    name_account_key: Pubkey,
+    name_owner_key: Pubkey,
+    refund_target: Pubkey,
+    account_infos: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let ix = delete(
+        name_service_program_id,
+        name_account_key,
+        name_owner_key,
+        refund_target,
+    )?;
+    invoke(&ix, account_infos)
+}
+
 pub fn delete(
     name_service_program_id: Pubkey,
     name_account_key: Pubkey,
@@ -0,0 +1,9 @@
+//! 이름 레지스트리(name service) 프로그램 크레이트 루트.
+
+pub mod error;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+#[cfg(not(feature = "no-entrypoint"))]
+pub mod entrypoint;
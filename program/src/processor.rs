@@ -0,0 +1,386 @@
+use {
+    crate::{
+        error::NameServiceError,
+        instruction::NameRegistryInstruction,
+        state::{
+            get_seeds_and_key, write_data, NameRecordHeader, CURRENT_DATA_VERSION,
+            LEGACY_HEADER_LEN,
+        },
+    },
+    borsh::BorshDeserialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, MAX_PERMITTED_DATA_LENGTH},
+        msg,
+        program::{invoke, invoke_signed},
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::{rent::Rent, Sysvar},
+    },
+};
+
+pub struct Processor {}
+
+impl Processor {
+    pub fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let instruction = NameRegistryInstruction::try_from_slice(instruction_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        match instruction {
+            NameRegistryInstruction::Create {
+                hashed_name,
+                lamports,
+                space,
+            } => {
+                msg!("Instruction: Create");
+                Self::process_create(program_id, accounts, hashed_name, lamports, space)
+            }
+            NameRegistryInstruction::Update { offset, data } => {
+                msg!("Instruction: Update");
+                Self::process_update(accounts, offset, data)
+            }
+            NameRegistryInstruction::Transfer { new_owner } => {
+                msg!("Instruction: Transfer");
+                Self::process_transfer(accounts, new_owner)
+            }
+            NameRegistryInstruction::Delete => {
+                msg!("Instruction: Delete");
+                Self::process_delete(accounts)
+            }
+            NameRegistryInstruction::Migrate => {
+                msg!("Instruction: Migrate");
+                Self::process_migrate(accounts)
+            }
+            NameRegistryInstruction::Realloc { new_space } => {
+                msg!("Instruction: Realloc");
+                Self::process_realloc(accounts, new_space)
+            }
+        }
+    }
+
+    fn process_create(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        hashed_name: Vec<u8>,
+        lamports: u64,
+        space: u32,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let system_program = next_account_info(account_info_iter)?;
+        let payer = next_account_info(account_info_iter)?;
+        let name_account = next_account_info(account_info_iter)?;
+        let name_owner = next_account_info(account_info_iter)?;
+        let name_class = next_account_info(account_info_iter)?;
+        let name_parent = next_account_info(account_info_iter)?;
+        let name_parent_owner = next_account_info(account_info_iter)?;
+
+        // 선택 계정은 placeholder(Pubkey::default)로 전달된다.
+        let name_class_opt = opt_key(name_class.key);
+        let name_parent_opt = opt_key(name_parent.key);
+
+        let (name_account_key, seeds, bump) = get_seeds_and_key(
+            program_id,
+            hashed_name,
+            name_class_opt.as_ref(),
+            name_parent_opt.as_ref(),
+        );
+        if name_account_key != *name_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // 이미 초기화된 계정은 거부
+        if !name_account.data_is_empty() {
+            return Err(NameServiceError::AlreadyInitialized.into());
+        }
+
+        // 요청한 lamports가 새 크기의 렌트 면제 최소치를 만족하는지 확인
+        let account_len = NameRecordHeader::LEN + space as usize;
+        let rent = Rent::get()?;
+        if !rent.is_exempt(lamports, account_len) {
+            return Err(NameServiceError::NotRentExempt.into());
+        }
+
+        // 클래스가 지정된 경우 서명 필요
+        if name_class_opt.is_some() && !name_class.is_signer {
+            return Err(NameServiceError::InvalidClassSigner.into());
+        }
+
+        // 부모가 지정된 경우 부모 소유자의 서명 필요
+        if name_parent_opt.is_some() {
+            let parent_header = NameRecordHeader::unpack_from_slice(&name_parent.data.borrow())?;
+            if !name_parent_owner.is_signer || parent_header.owner != *name_parent_owner.key {
+                return Err(NameServiceError::InvalidParentOwner.into());
+            }
+        }
+
+        // PDA 서명으로 계정 생성
+        let seed_slices: Vec<&[u8]> = seeds.chunks(32).collect();
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                name_account.key,
+                lamports,
+                account_len as u64,
+                program_id,
+            ),
+            &[payer.clone(), name_account.clone(), system_program.clone()],
+            &[&seed_slices],
+        )?;
+
+        // 헤더 기록: 버전과 bump를 함께 저장한다.
+        let header = NameRecordHeader {
+            data_version: CURRENT_DATA_VERSION,
+            parent_name: name_parent_opt.unwrap_or_default(),
+            owner: *name_owner.key,
+            class: name_class_opt.unwrap_or_default(),
+            bump_seed: bump,
+        };
+        header.pack_into_slice(&mut name_account.data.borrow_mut());
+        Ok(())
+    }
+
+    fn process_update(accounts: &[AccountInfo], offset: u32, data: Vec<u8>) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let name_account = next_account_info(account_info_iter)?;
+        let name_update_signer = next_account_info(account_info_iter)?;
+        let name_parent = next_account_info(account_info_iter).ok();
+
+        let header = NameRecordHeader::unpack_from_slice(&name_account.data.borrow())?;
+        // 이해하지 못하는 버전에는 쓰기를 거부한다(바이트 오해석 방지).
+        if !header.is_known_version() {
+            return Err(NameServiceError::UnknownDataVersion.into());
+        }
+        verify_update_authority(&header, name_update_signer, name_parent)?;
+
+        // 헤더 길이를 더한 시작 오프셋은 여기서 계산하고, 쓰기 범위 검사는 write_data가 수행한다
+        // (시작 + data.len()이 오버플로하면 NumericOverflow, 계정 길이를 넘으면 OffsetOutOfBounds).
+        let start = NameRecordHeader::LEN
+            .checked_add(offset as usize)
+            .ok_or(NameServiceError::NumericOverflow)?;
+        write_data(name_account, &data, start)?;
+        Ok(())
+    }
+
+    fn process_transfer(accounts: &[AccountInfo], new_owner: Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let name_account = next_account_info(account_info_iter)?;
+        let name_owner = next_account_info(account_info_iter)?;
+        let name_parent = next_account_info(account_info_iter).ok();
+
+        let mut header = NameRecordHeader::unpack_from_slice(&name_account.data.borrow())?;
+        if !header.is_known_version() {
+            return Err(NameServiceError::UnknownDataVersion.into());
+        }
+        verify_update_authority(&header, name_owner, name_parent)?;
+
+        header.owner = new_owner;
+        header.pack_into_slice(&mut name_account.data.borrow_mut());
+        Ok(())
+    }
+
+    fn process_delete(accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let name_account = next_account_info(account_info_iter)?;
+        let name_owner = next_account_info(account_info_iter)?;
+        let refund_target = next_account_info(account_info_iter)?;
+
+        let header = NameRecordHeader::unpack_from_slice(&name_account.data.borrow())?;
+        if !header.is_known_version() {
+            return Err(NameServiceError::UnknownDataVersion.into());
+        }
+        if !name_owner.is_signer || name_owner.key != &header.owner {
+            return Err(NameServiceError::InvalidOwner.into());
+        }
+
+        // 램포트를 환불 계정으로 옮기고 데이터를 0으로 지운다.
+        let mut source = name_account.lamports.borrow_mut();
+        let mut destination = refund_target.lamports.borrow_mut();
+        **destination = destination
+            .checked_add(**source)
+            .ok_or(NameServiceError::NumericOverflow)?;
+        **source = 0;
+        drop(source);
+        drop(destination);
+
+        let mut data = name_account.data.borrow_mut();
+        for byte in data.iter_mut() {
+            *byte = 0;
+        }
+        Ok(())
+    }
+
+    fn process_migrate(accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let name_account = next_account_info(account_info_iter)?;
+        let payer = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        // 이 프로그램의 마이그레이션은 v0 → v1 한 방향뿐이다. v0 레코드에는 버전 바이트가
+        // 없어 자식 레코드(parent_name != default)는 v0와 v1을 구분할 수 없으므로,
+        // 최상위 레코드만 대상으로 삼고 모호한 자식 레코드는 조용히 건너뛰지 않고
+        // 명시적으로 거부한다.
+        {
+            let data = name_account.data.borrow();
+            if data.len() < LEGACY_HEADER_LEN {
+                return Err(NameServiceError::Uninitialized.into());
+            }
+            if data[0] == CURRENT_DATA_VERSION {
+                // v1 레이아웃: parent_name은 data[1..33]. 최상위가 아니면 모호하므로 거부.
+                if data[1..33].iter().any(|&b| b != 0) {
+                    msg!("Migrate: child records are not migratable");
+                    return Err(ProgramError::InvalidArgument);
+                }
+                // 이미 최신인 최상위 레코드: 할 일 없음.
+                return Ok(());
+            }
+            // v0 레이아웃: parent_name은 data[0..32]. 최상위가 아니면 거부.
+            if data[0..32].iter().any(|&b| b != 0) {
+                msg!("Migrate: child records are not migratable");
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
+        // 최상위 v0 레코드: 옛 96바이트 레이아웃을 읽어 새 헤더를 만든다.
+        let upgraded = {
+            let data = name_account.data.borrow();
+            NameRecordHeader::migrate_in_place(&data)?
+        };
+
+        // 새 레이아웃은 버전/bump 바이트만큼(2바이트) 크다. 기존에 렌트 면제였던 레코드가
+        // 비면제 상태가 되지 않도록, 커진 크기의 렌트 차액을 자금 계정에서 먼저 채운다.
+        let delta = NameRecordHeader::LEN - LEGACY_HEADER_LEN;
+        let old_len = name_account.data_len();
+        let new_len = old_len + delta;
+
+        let rent = Rent::get()?;
+        let new_minimum_balance = rent.minimum_balance(new_len);
+        let current_balance = name_account.lamports();
+        if new_minimum_balance > current_balance {
+            let lamports_diff = new_minimum_balance
+                .checked_sub(current_balance)
+                .ok_or(NameServiceError::NumericOverflow)?;
+            invoke(
+                &system_instruction::transfer(payer.key, name_account.key, lamports_diff),
+                &[payer.clone(), name_account.clone(), system_program.clone()],
+            )?;
+        }
+
+        // 계정을 키우고 기존 데이터 영역을 새 헤더 뒤로 밀어준 뒤 헤더를 기록한다.
+        name_account.realloc(new_len, false)?;
+        {
+            let mut data = name_account.data.borrow_mut();
+            data.copy_within(LEGACY_HEADER_LEN..old_len, NameRecordHeader::LEN);
+        }
+        upgraded.pack_into_slice(&mut name_account.data.borrow_mut());
+        Ok(())
+    }
+
+    fn process_realloc(accounts: &[AccountInfo], new_space: u32) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let name_account = next_account_info(account_info_iter)?;
+        let payer = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let name_update_signer = next_account_info(account_info_iter)?;
+        let name_parent = next_account_info(account_info_iter).ok();
+
+        let header = NameRecordHeader::unpack_from_slice(&name_account.data.borrow())?;
+        if !header.is_known_version() {
+            return Err(NameServiceError::UnknownDataVersion.into());
+        }
+        verify_update_authority(&header, name_update_signer, name_parent)?;
+
+        // (a) 런타임 한계(10 MiB)를 넘는 요청은 거부한다.
+        let new_len = NameRecordHeader::LEN + new_space as usize;
+        if new_len > MAX_PERMITTED_DATA_LENGTH as usize {
+            return Err(NameServiceError::OutOfSpace.into());
+        }
+        // 한 명령에서의 증가량은 런타임이 `MAX_PERMITTED_DATA_INCREASE`(10 KiB)로 제한하므로,
+        // 이를 넘는 grow는 불투명한 런타임 오류 대신 명확한 에러로 먼저 거부한다.
+        let old_len = name_account.data_len();
+        if new_len > old_len && new_len - old_len > MAX_PERMITTED_DATA_INCREASE {
+            return Err(NameServiceError::OutOfSpace.into());
+        }
+
+        // (b) 새 크기의 렌트 면제 최소치를 계산해 램포트 차액을 정산한다.
+        let rent = Rent::get()?;
+        let new_minimum_balance = rent.minimum_balance(new_len);
+        let current_balance = name_account.lamports();
+        if new_minimum_balance > current_balance {
+            // 커짐: 부족분을 자금 계정(writable signer)에서 시스템 전송으로 채운다.
+            let lamports_diff = new_minimum_balance
+                .checked_sub(current_balance)
+                .ok_or(NameServiceError::NumericOverflow)?;
+            invoke(
+                &system_instruction::transfer(payer.key, name_account.key, lamports_diff),
+                &[payer.clone(), name_account.clone(), system_program.clone()],
+            )?;
+        } else if current_balance > new_minimum_balance {
+            // 줄어듦: 초과분을 환불 대상으로 돌려준다. 레코드는 프로그램 소유이므로 직접 이동.
+            let lamports_diff = current_balance
+                .checked_sub(new_minimum_balance)
+                .ok_or(NameServiceError::NumericOverflow)?;
+            let mut source = name_account.lamports.borrow_mut();
+            let mut destination = payer.lamports.borrow_mut();
+            **destination = destination
+                .checked_add(lamports_diff)
+                .ok_or(NameServiceError::NumericOverflow)?;
+            **source = new_minimum_balance;
+        }
+
+        // (c) 크기를 조정하고 새로 추가된 바이트를 0으로 초기화한다.
+        name_account.realloc(new_len, true)?;
+        Ok(())
+    }
+}
+
+/// 선택 계정의 키가 placeholder(default)이면 `None`을 돌려준다.
+fn opt_key(key: &Pubkey) -> Option<Pubkey> {
+    if *key == Pubkey::default() {
+        None
+    } else {
+        Some(*key)
+    }
+}
+
+/// `Update`/`Transfer`/`Realloc`가 공유하는 권한 검사:
+/// 서명자가 레코드 소유자이거나, (클래스가 설정된 경우) 클래스이거나,
+/// (부모가 있는 경우) 부모 레코드의 소유자여야 한다.
+fn verify_update_authority(
+    header: &NameRecordHeader,
+    signer: &AccountInfo,
+    parent: Option<&AccountInfo>,
+) -> ProgramResult {
+    // 소유자
+    if signer.key == &header.owner {
+        if !signer.is_signer {
+            return Err(NameServiceError::InvalidOwner.into());
+        }
+        return Ok(());
+    }
+    // 클래스(설정된 경우)
+    if header.class != Pubkey::default() && signer.key == &header.class {
+        if !signer.is_signer {
+            return Err(NameServiceError::InvalidClassSigner.into());
+        }
+        return Ok(());
+    }
+    // 부모 레코드의 소유자(부모가 있는 경우)
+    if header.parent_name != Pubkey::default() {
+        let parent = parent.ok_or(NameServiceError::InvalidParentOwner)?;
+        let parent_header = NameRecordHeader::unpack_from_slice(&parent.data.borrow())?;
+        if &parent_header.owner == signer.key {
+            if !signer.is_signer {
+                return Err(NameServiceError::InvalidParentOwner.into());
+            }
+            return Ok(());
+        }
+        return Err(NameServiceError::InvalidParentOwner.into());
+    }
+    Err(NameServiceError::InvalidOwner.into())
+}
@@ -1,3 +1,4 @@
+use crate::error::NameServiceError;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::AccountInfo,
@@ -9,17 +10,29 @@ use solana_program::{
 use std::cell::RefMut;
 use std::io::Cursor;
 
+/// 현재 프로그램이 이해하는 `NameRecordHeader` 레이아웃 버전.
+/// 버전이 0인 레코드는 `data_version` 바이트가 도입되기 이전에 생성된 것으로 간주한다.
+pub const CURRENT_DATA_VERSION: u8 = 1;
+
+/// v0(버전/bump 바이트가 없던) 레이아웃의 헤더 길이: parent_name/owner/class 3개 × 32바이트.
+pub const LEGACY_HEADER_LEN: usize = 96;
+
 #[derive(Clone, Debug, BorshDeserialize, BorshSerialize, PartialEq)]
 pub struct NameRecordHeader {
+    /// 레이아웃 판별자. 헤더의 첫 바이트로 직렬화되며 `Migrate`로 올려준다.
+    pub data_version: u8,
     pub parent_name: Pubkey,
     pub owner: Pubkey,
     pub class: Pubkey,
+    /// PDA 유도 시 찾은 canonical bump. `Create`에서 저장해 두어 이후 CPI 서명 시
+    /// 256회 탐색 없이 `create_program_address`로 바로 재서명할 수 있게 한다.
+    pub bump_seed: u8,
 }
 
 impl Sealed for NameRecordHeader {}
 
 impl Pack for NameRecordHeader {
-    const LEN: usize = 96;
+    const LEN: usize = 98;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut writer = Cursor::new(dst);
@@ -27,7 +40,9 @@ impl Pack for NameRecordHeader {
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        NameRecordHeader::try_from_slice(src).map_err(|_| {
+        // 레코드는 헤더 뒤에 데이터 영역(`space` 바이트)이 붙어 있으므로, 슬라이스 전체를
+        // 소비하는 `try_from_slice`(후행 바이트가 있으면 실패)가 아니라 헤더 길이만큼만 읽는다.
+        NameRecordHeader::deserialize(&mut &src[..]).map_err(|_| {
             msg!("Failed to deserialize name record");
             ProgramError::InvalidAccountData
         })
@@ -40,9 +55,92 @@ impl IsInitialized for NameRecordHeader {
     }
 }
 
-pub fn write_data(account_: &AccountInfo, input: &[u8], offset: usize) {
+impl NameRecordHeader {
+    /// 프로세서가 이 레코드의 바이트 배치를 이해하는지 검사한다.
+    /// 알 수 없는 버전에 대해 쓰기/수정을 시도하면 바이트를 잘못 해석할 수 있으므로
+    /// 이해하지 못하는 버전은 거부한다.
+    pub fn is_known_version(&self) -> bool {
+        self.data_version == CURRENT_DATA_VERSION
+    }
+
+    /// v0 레코드(버전/bump 바이트가 없던 96바이트 레이아웃: parent_name/owner/class)의
+    /// 원시 바이트를 읽어 최신 레이아웃 헤더로 올린다.
+    ///
+    /// `data_version` 바이트가 헤더 맨 앞에 새로 끼어들기 때문에 v0 계정을 그대로
+    /// `try_from_slice`로 역직렬화하면 모든 필드가 어긋난다. 그래서 마이그레이션은
+    /// 파싱된 구조체가 아니라 옛 레이아웃의 원시 바이트에서 직접 읽어야 한다.
+    /// `owner`/`class`/`parent_name`은 보존하고, 새로 추가된 `bump_seed`는 0으로,
+    /// `data_version`은 현재 버전으로 채운다.
+    pub fn migrate_in_place(legacy_src: &[u8]) -> Result<Self, ProgramError> {
+        if legacy_src.len() < LEGACY_HEADER_LEN {
+            return Err(NameServiceError::Uninitialized.into());
+        }
+        let parent_name = Pubkey::try_from(&legacy_src[0..32])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let owner = Pubkey::try_from(&legacy_src[32..64])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let class = Pubkey::try_from(&legacy_src[64..96])
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        Ok(Self {
+            data_version: CURRENT_DATA_VERSION,
+            parent_name,
+            owner,
+            class,
+            bump_seed: 0,
+        })
+    }
+
+    /// 저장된 `bump_seed`와 `class`/`parent_name`으로 canonical signer seed 묶음을
+    /// 재구성한다. bump는 헤더에 보존된 값을 그대로 읽으므로 호출부가 따로 넘길 필요가
+    /// 없다(온체인에 저장되지 않는 `hashed_name`만 넘긴다). 반환된 [`SignerSeeds`]의
+    /// `as_slices()`를 `invoke_signed`/`create_program_address`에 그대로 전달한다.
+    pub fn signer_seeds<'a>(&self, hashed_name: &'a [u8]) -> SignerSeeds<'a> {
+        SignerSeeds {
+            hashed_name,
+            class: self.class,
+            parent_name: self.parent_name,
+            bump: [self.bump_seed],
+        }
+    }
+}
+
+/// [`NameRecordHeader::signer_seeds`]가 돌려주는 소유형 시드 묶음.
+/// 저장된 bump 바이트를 안에 들고 있어 `as_slices()`가 돌려주는 슬라이스 배열의
+/// 수명을 보장한다.
+pub struct SignerSeeds<'a> {
+    hashed_name: &'a [u8],
+    class: Pubkey,
+    parent_name: Pubkey,
+    bump: [u8; 1],
+}
+
+impl SignerSeeds<'_> {
+    /// `invoke_signed`/`create_program_address`에 넘길 canonical seed 슬라이스 배열.
+    pub fn as_slices(&self) -> [&[u8]; 4] {
+        [
+            self.hashed_name,
+            self.class.as_ref(),
+            self.parent_name.as_ref(),
+            &self.bump,
+        ]
+    }
+}
+
+pub fn write_data(
+    account_: &AccountInfo,
+    input: &[u8],
+    offset: usize,
+) -> Result<(), ProgramError> {
+    // 범위를 벗어난/오버플로하는 쓰기는 슬라이스 인덱싱 전에 거부해 패닉을 막는다.
+    let end = offset
+        .checked_add(input.len())
+        .ok_or(NameServiceError::NumericOverflow)?;
     let mut account_data: RefMut<&mut [u8]> = account_.data.borrow_mut();
-    account_data[offset..offset + input.len()].copy_from_slice(input);
+    if end > account_data.len() {
+        return Err(NameServiceError::OffsetOutOfBounds.into());
+    }
+    account_data[offset..end].copy_from_slice(input);
+    Ok(())
 }
 
 pub fn get_seeds_and_key(
@@ -50,7 +148,7 @@ pub fn get_seeds_and_key(
     hashed_name: Vec<u8>,
     name_class_opt: Option<&Pubkey>,
     parent_name_address_opt: Option<&Pubkey>,
-) -> (Pubkey, Vec<u8>) {
+) -> (Pubkey, Vec<u8>, u8) {
     let mut seeds_vec: Vec<u8> = hashed_name;
 
     let name_class: Pubkey = name_class_opt.cloned().unwrap_or_default();
@@ -63,5 +161,7 @@ pub fn get_seeds_and_key(
     let (name_account_key, bump) = Pubkey::find_program_address(&seed_slices, program_id);
     seeds_vec.push(bump);
 
-    (name_account_key, seeds_vec)
+    // bump을 함께 돌려주어 `Create` 처리부가 헤더에 저장하고, 클라이언트(`create` 빌더 호출부)도
+    // 오프체인에서 다시 계산하지 않고 재사용할 수 있게 한다.
+    (name_account_key, seeds_vec, bump)
 }
\ No newline at end of file
@@ -0,0 +1,191 @@
+#![cfg(feature = "test-sbf")]
+
+use {
+    borsh::BorshDeserialize,
+    name_service::{
+        instruction::{
+            create, delete, migrate, realloc, transfer, update, NameRegistryInstruction,
+        },
+        processor::Processor,
+        state::{get_seeds_and_key, NameRecordHeader, CURRENT_DATA_VERSION, LEGACY_HEADER_LEN},
+    },
+    solana_program::pubkey::Pubkey,
+    solana_program_test::{processor, ProgramTest, ProgramTestContext},
+    solana_sdk::{
+        account::Account,
+        instruction::Instruction,
+        rent::Rent,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+
+// 테스트용 해시 이름(32바이트 = 시드 한 청크).
+fn hashed_name() -> Vec<u8> {
+    vec![7u8; 32]
+}
+
+fn program_test(program_id: Pubkey) -> ProgramTest {
+    ProgramTest::new(
+        "name_service",
+        program_id,
+        processor!(Processor::process_instruction),
+    )
+}
+
+async fn read_account(ctx: &mut ProgramTestContext, key: &Pubkey) -> Account {
+    ctx.banks_client
+        .get_account(*key)
+        .await
+        .unwrap()
+        .expect("record account missing")
+}
+
+async fn read_header(ctx: &mut ProgramTestContext, key: &Pubkey) -> NameRecordHeader {
+    let account = read_account(ctx, key).await;
+    NameRecordHeader::deserialize(&mut &account.data[..]).unwrap()
+}
+
+async fn send(ctx: &mut ProgramTestContext, ixs: &[Instruction], extra: &[&Keypair]) {
+    let tx = signed_tx(ctx, ixs, extra);
+    ctx.banks_client.process_transaction(tx).await.unwrap();
+}
+
+async fn send_expect_err(ctx: &mut ProgramTestContext, ixs: &[Instruction], extra: &[&Keypair]) {
+    let tx = signed_tx(ctx, ixs, extra);
+    assert!(ctx.banks_client.process_transaction(tx).await.is_err());
+}
+
+fn signed_tx(ctx: &ProgramTestContext, ixs: &[Instruction], extra: &[&Keypair]) -> Transaction {
+    let mut signers: Vec<&Keypair> = vec![&ctx.payer];
+    signers.extend_from_slice(extra);
+    Transaction::new_signed_with_payer(ixs, Some(&ctx.payer.pubkey()), &signers, ctx.last_blockhash)
+}
+
+#[tokio::test]
+async fn lifecycle_create_update_transfer_realloc_delete() {
+    let program_id = Pubkey::new_unique();
+    let mut ctx = program_test(program_id).start_with_context().await;
+
+    let owner = Keypair::new();
+    let space: u32 = 16;
+    let (name_key, _seeds, expected_bump) = get_seeds_and_key(&program_id, hashed_name(), None, None);
+    let lamports = Rent::default().minimum_balance(NameRecordHeader::LEN + space as usize);
+
+    // --- Create ---
+    let create_data = NameRegistryInstruction::Create {
+        hashed_name: hashed_name(),
+        lamports,
+        space,
+    };
+    let (create_ix, bump) = create(
+        program_id,
+        create_data,
+        name_key,
+        owner.pubkey(),
+        ctx.payer.pubkey(),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(bump, expected_bump);
+    send(&mut ctx, &[create_ix], &[]).await;
+
+    let header = read_header(&mut ctx, &name_key).await;
+    assert_eq!(header.data_version, CURRENT_DATA_VERSION);
+    assert_eq!(header.owner, owner.pubkey());
+    assert_eq!(header.bump_seed, bump);
+    assert_eq!(
+        read_account(&mut ctx, &name_key).await.data.len(),
+        NameRecordHeader::LEN + space as usize
+    );
+
+    // --- Update: 헤더 뒤 데이터 영역에 기록 ---
+    let payload = vec![1u8, 2, 3, 4];
+    let update_ix = update(program_id, 0, payload.clone(), name_key, owner.pubkey(), None).unwrap();
+    send(&mut ctx, &[update_ix], &[&owner]).await;
+    let data = read_account(&mut ctx, &name_key).await.data;
+    assert_eq!(
+        &data[NameRecordHeader::LEN..NameRecordHeader::LEN + payload.len()],
+        payload.as_slice()
+    );
+
+    // --- Update: 범위를 벗어난 오프셋은 거부 ---
+    let bad = update(program_id, space, vec![9u8], name_key, owner.pubkey(), None).unwrap();
+    send_expect_err(&mut ctx, &[bad], &[&owner]).await;
+
+    // --- Transfer ---
+    let new_owner = Keypair::new();
+    let transfer_ix =
+        transfer(program_id, new_owner.pubkey(), name_key, owner.pubkey(), None).unwrap();
+    send(&mut ctx, &[transfer_ix], &[&owner]).await;
+    assert_eq!(read_header(&mut ctx, &name_key).await.owner, new_owner.pubkey());
+
+    // --- Realloc: 키우기 ---
+    let realloc_ix = realloc(
+        program_id,
+        128,
+        name_key,
+        ctx.payer.pubkey(),
+        new_owner.pubkey(),
+        None,
+    )
+    .unwrap();
+    send(&mut ctx, &[realloc_ix], &[&new_owner]).await;
+    assert_eq!(
+        read_account(&mut ctx, &name_key).await.data.len(),
+        NameRecordHeader::LEN + 128
+    );
+
+    // --- Delete ---
+    let refund = Keypair::new();
+    let delete_ix = delete(program_id, name_key, new_owner.pubkey(), refund.pubkey()).unwrap();
+    send(&mut ctx, &[delete_ix], &[&new_owner]).await;
+    let account = ctx.banks_client.get_account(name_key).await.unwrap();
+    assert!(account.map(|a| a.lamports == 0).unwrap_or(true));
+}
+
+#[tokio::test]
+async fn migrate_top_level_v0_record() {
+    let program_id = Pubkey::new_unique();
+    let mut test = program_test(program_id);
+
+    let owner = Pubkey::new_unique();
+    let class = Pubkey::new_unique();
+    let space = 8usize;
+
+    // v0 레이아웃(버전/bump 없음): parent(default) ++ owner ++ class ++ data.
+    let mut v0 = vec![0u8; LEGACY_HEADER_LEN + space];
+    v0[32..64].copy_from_slice(owner.as_ref());
+    v0[64..96].copy_from_slice(class.as_ref());
+    // 새 크기 기준으로 미리 렌트 면제가 되도록 충전(마이그레이션이 차액도 채운다).
+    let lamports = Rent::default().minimum_balance(NameRecordHeader::LEN + space);
+
+    let (name_key, _seeds, _bump) = get_seeds_and_key(&program_id, hashed_name(), None, None);
+    test.add_account(
+        name_key,
+        Account {
+            lamports,
+            data: v0,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let mut ctx = test.start_with_context().await;
+    let migrate_ix = migrate(program_id, name_key, ctx.payer.pubkey()).unwrap();
+    send(&mut ctx, &[migrate_ix], &[]).await;
+
+    let header = read_header(&mut ctx, &name_key).await;
+    assert_eq!(header.data_version, CURRENT_DATA_VERSION);
+    assert_eq!(header.owner, owner);
+    assert_eq!(header.class, class);
+    assert_eq!(header.parent_name, Pubkey::default());
+    assert_eq!(header.bump_seed, 0);
+    assert_eq!(
+        read_account(&mut ctx, &name_key).await.data.len(),
+        NameRecordHeader::LEN + space
+    );
+}